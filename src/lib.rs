@@ -1,3 +1,6 @@
+use std::mem::MaybeUninit;
+use std::ops::Range;
+
 /// Rolling Buffer that will fill to capacity and then start overwriting the oldest data first.
 ///
 /// Example
@@ -12,32 +15,30 @@
 ///     assert_eq!(vec_out, iter_out);
 /// }
 /// ```
-pub struct RollingBuffer<T>
-where
-    T: Copy + Default,
-{
+pub struct RollingBuffer<T> {
     capacity: usize,
-    buffer: Vec<T>,
+    buffer: Box<[MaybeUninit<T>]>,
     current_index: usize,
     count: usize,
 }
 
-impl<T> RollingBuffer<T>
-where
-    T: Copy + Default,
-{
+impl<T> RollingBuffer<T> {
     /// Create a new buffer and allocate memory for it immediately up to a size of capacity.
     pub fn new(capacity: usize) -> RollingBuffer<T> {
         RollingBuffer {
             capacity,
-            buffer: vec![Default::default(); capacity],
+            buffer: (0..capacity).map(|_| MaybeUninit::uninit()).collect(),
             current_index: 0,
             count: 0,
         }
     }
     /// Add a new value to the buffer, if it has reached capacity it will overwrite the oldest datapoint.
     pub fn add(&mut self, value: T) -> bool {
-        self.buffer[self.current_index] = value;
+        if self.count == self.capacity {
+            // This slot already holds a live value that we're about to overwrite.
+            unsafe { self.buffer[self.current_index].assume_init_drop() };
+        }
+        self.buffer[self.current_index] = MaybeUninit::new(value);
         self.current_index = (self.current_index + 1) % self.capacity;
         self.count = std::cmp::min(self.count + 1, self.capacity);
         self.is_full()
@@ -50,26 +51,68 @@ where
     pub fn is_full(&self) -> bool {
         self.count == self.capacity
     }
-    /// Return a single value from the buffer at the position requested.  
+    /// Return a single value from the buffer at the position requested.
     /// If the buffer has hit capacity this will be the position in the valid data -
-    /// where index 0 is the newest datapoint, 1 is the second newest, and so on.
+    /// where index 0 is the oldest datapoint, 1 is the second oldest, and so on.
     ///
-    /// If index > capacity, returns the oldest valid number
+    /// If index >= len(), returns the newest valid value.
+    ///
+    /// Panics if the buffer is empty.
     pub fn get(&self, index: usize) -> &T {
-        if index >= self.capacity {
-            return &self.buffer[(self.current_index + self.capacity - 1 + self.capacity
-                - self.count)
-                % self.capacity];
+        assert!(self.count > 0, "RollingBuffer::get: buffer is empty");
+        let index = std::cmp::min(index, self.count - 1);
+        let idx = (self.current_index + index + self.capacity - self.count) % self.capacity;
+        unsafe { &*self.buffer[idx].as_ptr() }
+    }
+    /// Mutable counterpart to [`RollingBuffer::get`].
+    ///
+    /// Panics if the buffer is empty.
+    pub fn get_mut(&mut self, index: usize) -> &mut T {
+        assert!(self.count > 0, "RollingBuffer::get_mut: buffer is empty");
+        let index = std::cmp::min(index, self.count - 1);
+        let idx = (self.current_index + index + self.capacity - self.count) % self.capacity;
+        unsafe { &mut *self.buffer[idx].as_mut_ptr() }
+    }
+    /// Remove and return the oldest valid value, if any.
+    fn take_oldest(&mut self) -> Option<T> {
+        if self.count == 0 {
+            return None;
         }
-        &self.buffer[(self.current_index + index + self.capacity - self.count) % self.capacity]
+        let tail_start = (self.current_index + self.capacity - self.count) % self.capacity;
+        let value = unsafe { self.buffer[tail_start].as_ptr().read() };
+        self.count -= 1;
+        Some(value)
     }
-    /// Return all of the valid values in the buffer, in the order they were added as a Vector.
-    pub fn values(&self) -> Vec<T> {
-        let mut values = Vec::with_capacity(self.count);
-        for i in 0..self.count {
-            values.push(*self.get(i));
+    /// The index ranges of the internal buffer that currently hold initialized values,
+    /// in logical (oldest-to-newest) order. The second range is empty unless the live
+    /// region wraps around the end of the buffer.
+    fn live_ranges(&self) -> (Range<usize>, Range<usize>) {
+        if self.count == 0 {
+            return (0..0, 0..0);
+        }
+        let tail_start = (self.current_index + self.capacity - self.count) % self.capacity;
+        if tail_start + self.count <= self.capacity {
+            (tail_start..tail_start + self.count, 0..0)
+        } else {
+            (tail_start..self.capacity, 0..self.current_index)
+        }
+    }
+    /// Return the valid data as at most two contiguous sub-slices of the internal buffer,
+    /// in logical (oldest-to-newest) order.
+    ///
+    /// If the live region doesn't wrap, the second slice is empty. Otherwise the first
+    /// slice runs from the tail to the end of the buffer and the second from index 0 up
+    /// to `current_index`.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.live_ranges();
+        // Safety: `live_ranges` only ever returns ranges over slots that `add`/`add_slice`
+        // have initialized and that have not since been dropped.
+        unsafe {
+            (
+                slice_assume_init(&self.buffer[first]),
+                slice_assume_init(&self.buffer[second]),
+            )
         }
-        values
     }
     /// Return all of the vald values in the buffer, in the order they were added, as an Iterator.
     ///
@@ -79,7 +122,238 @@ where
     /// ```
     ///
     pub fn values_iter(&self) -> impl Iterator<Item = &T> {
-        (0..self.count).map(move |i| self.get(i))
+        let (first, second) = self.as_slices();
+        first.iter().chain(second.iter())
+    }
+    /// Logically discard the `n` oldest valid entries, dropping each one in place.
+    pub fn skip_oldest(&mut self, n: usize) {
+        let n = std::cmp::min(n, self.count);
+        let tail_start = (self.current_index + self.capacity - self.count) % self.capacity;
+        for i in 0..n {
+            let idx = (tail_start + i) % self.capacity;
+            unsafe { self.buffer[idx].assume_init_drop() };
+        }
+        self.count -= n;
+    }
+    /// Build a buffer of the given capacity, feeding every item of `iter` through [`RollingBuffer::add`].
+    ///
+    /// `capacity` isn't known from the iterator, so unlike `std::iter::FromIterator` this takes
+    /// it explicitly; items beyond `capacity` simply overwrite the oldest ones as usual.
+    pub fn from_iter_with_capacity<I: IntoIterator<Item = T>>(capacity: usize, iter: I) -> Self {
+        let mut buffer = Self::new(capacity);
+        for item in iter {
+            buffer.add(item);
+        }
+        buffer
+    }
+    /// Remove all valid values, returning them oldest-first, and reset the buffer to empty.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { buffer: self }
+    }
+}
+
+impl<T: Clone> RollingBuffer<T> {
+    /// Return all of the valid values in the buffer, in the order they were added as a Vector.
+    pub fn values(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.count);
+        let (first, second) = self.as_slices();
+        values.extend_from_slice(first);
+        values.extend_from_slice(second);
+        values
+    }
+    /// Every contiguous length-`size` window of the currently valid data, oldest-to-newest,
+    /// analogous to slice `windows` but correct across the wrap point.
+    ///
+    /// Built directly on [`RollingBuffer::as_slices`]: a window entirely within one half is
+    /// returned without touching the other, and only a window that straddles the wrap seam
+    /// pays for stitching the two halves together.
+    ///
+    /// Yields `len().saturating_sub(size) + 1` windows when `0 < size <= len()`, and none otherwise
+    /// (in particular, `size == 0` yields no windows).
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+        let (first, second) = self.as_slices();
+        let total_len = first.len() + second.len();
+        let window_count = if size == 0 || size > total_len {
+            0
+        } else {
+            total_len - size + 1
+        };
+        (0..window_count).map(move |start| {
+            if start + size <= first.len() {
+                first[start..start + size].to_vec()
+            } else if start >= first.len() {
+                let second_start = start - first.len();
+                second[second_start..second_start + size].to_vec()
+            } else {
+                let from_first = first.len() - start;
+                let mut window = Vec::with_capacity(size);
+                window.extend_from_slice(&first[start..]);
+                window.extend_from_slice(&second[..size - from_first]);
+                window
+            }
+        })
+    }
+}
+
+impl<T: Copy> RollingBuffer<T> {
+    /// Append many values at once, wrapping and overwriting the oldest data as needed.
+    ///
+    /// If `data` is longer than `capacity`, only the last `capacity` elements are kept.
+    pub fn add_slice(&mut self, data: &[T]) {
+        let data = if data.len() > self.capacity {
+            &data[data.len() - self.capacity..]
+        } else {
+            data
+        };
+        let mut remaining = data;
+        while !remaining.is_empty() {
+            let space_to_end = self.capacity - self.current_index;
+            let chunk_len = std::cmp::min(space_to_end, remaining.len());
+            let (chunk, rest) = remaining.split_at(chunk_len);
+            // Safety: `chunk` and the destination region are both `chunk_len` long and,
+            // since `T: Copy`, overwriting a live slot here needs no drop of the old value.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    chunk.as_ptr(),
+                    self.buffer[self.current_index..].as_mut_ptr() as *mut T,
+                    chunk_len,
+                );
+            }
+            self.current_index = (self.current_index + chunk_len) % self.capacity;
+            self.count = std::cmp::min(self.count + chunk_len, self.capacity);
+            remaining = rest;
+        }
+    }
+    /// Copy the most recent `out.len()` values into `out`, oldest-to-newest, returning how many were written.
+    ///
+    /// If fewer than `out.len()` values are valid, only `len()` values are written.
+    pub fn read_recent(&self, out: &mut [T]) -> usize {
+        let n = std::cmp::min(out.len(), self.count);
+        // Index (within the valid region) of the oldest element to copy.
+        let skip = self.count - n;
+        let (first, second) = self.as_slices();
+        if skip < first.len() {
+            let from_first = first.len() - skip;
+            out[..from_first].copy_from_slice(&first[skip..]);
+            out[from_first..n].copy_from_slice(&second[..n - from_first]);
+        } else {
+            let second_start = skip - first.len();
+            out[..n].copy_from_slice(&second[second_start..second_start + n]);
+        }
+        n
+    }
+}
+
+/// Lets `RollingBuffer<u8>` act as a fixed-size "keep the last N bytes" sink: writes never
+/// fail, they just overwrite the oldest bytes once the buffer is full.
+impl std::io::Write for RollingBuffer<u8> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.add_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Drains from the oldest valid byte forward, so repeated reads eventually consume
+/// every byte still held in the buffer; bytes already overwritten by a later write are gone.
+impl std::io::Read for RollingBuffer<u8> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = std::cmp::min(buf.len(), self.count);
+        let (first, second) = self.as_slices();
+        if n <= first.len() {
+            buf[..n].copy_from_slice(&first[..n]);
+        } else {
+            buf[..first.len()].copy_from_slice(first);
+            buf[first.len()..n].copy_from_slice(&second[..n - first.len()]);
+        }
+        self.skip_oldest(n);
+        Ok(n)
+    }
+}
+
+impl<T> Drop for RollingBuffer<T> {
+    fn drop(&mut self) {
+        let (first, second) = self.live_ranges();
+        for idx in first.chain(second) {
+            unsafe { self.buffer[idx].assume_init_drop() };
+        }
+    }
+}
+
+/// Safety: every index in `slice` must hold an initialized `T`.
+unsafe fn slice_assume_init<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const T, slice.len()) }
+}
+
+/// Owning iterator over a [`RollingBuffer`], yielding values oldest-to-newest.
+pub struct IntoIter<T>(RollingBuffer<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.0.take_oldest()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.count, Some(self.0.count))
+    }
+}
+
+impl<T> IntoIterator for RollingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+/// Draining iterator over a [`RollingBuffer`], produced by [`RollingBuffer::drain`].
+pub struct Drain<'a, T> {
+    buffer: &'a mut RollingBuffer<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.buffer.take_oldest()
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        for _ in self.by_ref() {}
+        self.buffer.current_index = 0;
+    }
+}
+
+impl<T> std::ops::Index<usize> for RollingBuffer<T> {
+    type Output = T;
+    /// Counts from the newest end, so `buff[0]` is the newest element, `buff[1]` the
+    /// second newest, and so on.
+    ///
+    /// Panics if `index >= len()`, matching `VecDeque`'s `Index` impl.
+    fn index(&self, index: usize) -> &T {
+        assert!(
+            index < self.count,
+            "RollingBuffer index out of bounds: index {} >= len {}",
+            index,
+            self.count
+        );
+        self.get(self.count - 1 - index)
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for RollingBuffer<T> {
+    /// Panics if `index >= len()`, matching `VecDeque`'s `IndexMut` impl.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        assert!(
+            index < self.count,
+            "RollingBuffer index out of bounds: index {} >= len {}",
+            index,
+            self.count
+        );
+        self.get_mut(self.count - 1 - index)
     }
 }
 
@@ -165,4 +439,204 @@ mod tests {
             assert_eq!(vec_out, iter_out);
         }
     }
+    #[test]
+    fn test_add_slice_wraps_and_overwrites() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3]);
+        assert_eq!(buff.values(), vec![1, 2, 3]);
+        buff.add_slice(&[4, 5, 6, 7]);
+        assert_eq!(buff.values(), vec![3, 4, 5, 6, 7]);
+    }
+    #[test]
+    fn test_add_slice_larger_than_capacity_keeps_last() {
+        let mut buff = RollingBuffer::new(5);
+        let data: Vec<i32> = (0..12).collect();
+        buff.add_slice(&data);
+        assert_eq!(buff.values(), vec![7, 8, 9, 10, 11]);
+    }
+    #[test]
+    fn test_read_recent() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        let mut out = [0; 3];
+        let n = buff.read_recent(&mut out);
+        assert_eq!(n, 3);
+        assert_eq!(out, [5, 6, 7]);
+        let mut out = [0; 10];
+        let n = buff.read_recent(&mut out);
+        assert_eq!(n, 5);
+        assert_eq!(&out[..5], &[3, 4, 5, 6, 7]);
+    }
+    #[test]
+    fn test_as_slices_no_wrap() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3]);
+        let (first, second) = buff.as_slices();
+        assert_eq!(first, &[1, 2, 3]);
+        assert!(second.is_empty());
+    }
+    #[test]
+    fn test_as_slices_wrapped() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        let (first, second) = buff.as_slices();
+        let mut combined = first.to_vec();
+        combined.extend_from_slice(second);
+        assert_eq!(combined, vec![3, 4, 5, 6, 7]);
+    }
+    #[test]
+    fn test_drop_counts_each_element_once() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct DropCounter(Rc<RefCell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                *self.0.borrow_mut() += 1;
+            }
+        }
+
+        let drops = Rc::new(RefCell::new(0));
+        {
+            let mut buff = RollingBuffer::new(5);
+            for _ in 0..17 {
+                buff.add(DropCounter(drops.clone()));
+            }
+            // 12 of the 17 pushes overwrote an already-live slot; the other 5 are
+            // still live and are dropped when `buff` itself goes out of scope.
+            assert_eq!(*drops.borrow(), 12);
+        }
+        assert_eq!(*drops.borrow(), 17);
+    }
+    #[test]
+    #[should_panic(expected = "buffer is empty")]
+    fn test_get_on_empty_buffer_panics() {
+        let buff: RollingBuffer<i32> = RollingBuffer::new(5);
+        buff.get(0);
+    }
+    #[test]
+    fn test_get_clamps_to_count_not_capacity() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add(10);
+        buff.add(20);
+        // Only 2 of 5 slots are live; any index >= count must clamp to the
+        // newest valid value rather than reading an uninitialized slot.
+        assert_eq!(*buff.get(4), 20);
+        *buff.get_mut(4) = 99;
+        assert_eq!(*buff.get(1), 99);
+    }
+    #[test]
+    fn test_non_copy_values() {
+        let mut buff = RollingBuffer::new(3);
+        buff.add(String::from("a"));
+        buff.add(String::from("b"));
+        buff.add(String::from("c"));
+        buff.add(String::from("d"));
+        assert_eq!(buff.values(), vec!["b", "c", "d"]);
+    }
+    #[test]
+    fn test_write_past_capacity_then_read_recent_bytes() {
+        use std::io::{Read, Write};
+        let mut buff: RollingBuffer<u8> = RollingBuffer::new(8);
+        let written = buff.write(b"0123456789abcdef").unwrap();
+        assert_eq!(written, 16);
+        assert_eq!(buff.len(), 8);
+        let mut out = [0u8; 8];
+        let read = buff.read(&mut out).unwrap();
+        assert_eq!(read, 8);
+        assert_eq!(&out, b"89abcdef");
+        assert_eq!(buff.len(), 0);
+    }
+    #[test]
+    fn test_into_iter_yields_oldest_to_newest() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        let values: Vec<_> = buff.into_iter().collect();
+        assert_eq!(values, vec![3, 4, 5, 6, 7]);
+    }
+    #[test]
+    fn test_from_iter_with_capacity() {
+        let buff = RollingBuffer::from_iter_with_capacity(5, 0..12);
+        assert_eq!(buff.values(), vec![7, 8, 9, 10, 11]);
+    }
+    #[test]
+    fn test_index_newest_first() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3]);
+        assert_eq!(buff[0], 3);
+        assert_eq!(buff[1], 2);
+        assert_eq!(buff[2], 1);
+        buff[0] = 30;
+        assert_eq!(buff.values(), vec![1, 2, 30]);
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_on_empty_buffer_panics() {
+        let buff: RollingBuffer<i32> = RollingBuffer::new(5);
+        let _ = buff[0];
+    }
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds_panics() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3]);
+        let _ = buff[3];
+    }
+    #[test]
+    fn test_drain_yields_all_and_empties_buffer() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        let drained: Vec<_> = buff.drain().collect();
+        assert_eq!(drained, vec![3, 4, 5, 6, 7]);
+        assert_eq!(buff.len(), 0);
+        assert_eq!(buff.values(), Vec::<i32>::new());
+    }
+    #[test]
+    fn test_drain_partial_consumption_still_empties_buffer() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5]);
+        {
+            let mut drain = buff.drain();
+            assert_eq!(drain.next(), Some(1));
+        }
+        assert_eq!(buff.len(), 0);
+    }
+    #[test]
+    fn test_windows_count() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5]);
+        let windows: Vec<_> = buff.windows(3).collect();
+        assert_eq!(windows.len(), buff.len().saturating_sub(3) + 1);
+        assert_eq!(
+            windows,
+            vec![vec![1, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]
+        );
+    }
+    #[test]
+    fn test_windows_across_wrap_seam() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5, 6, 7]); // values: [3, 4, 5, 6, 7], wraps internally
+        let windows: Vec<_> = buff.windows(3).collect();
+        assert_eq!(windows.len(), buff.len().saturating_sub(3) + 1);
+        assert_eq!(
+            windows,
+            vec![vec![3, 4, 5], vec![4, 5, 6], vec![5, 6, 7]]
+        );
+    }
+    #[test]
+    fn test_windows_larger_than_len_is_empty() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2]);
+        assert_eq!(buff.windows(3).count(), 0);
+    }
+    #[test]
+    fn test_skip_oldest() {
+        let mut buff = RollingBuffer::new(5);
+        buff.add_slice(&[1, 2, 3, 4, 5]);
+        buff.skip_oldest(2);
+        assert_eq!(buff.len(), 3);
+        assert_eq!(buff.values(), vec![3, 4, 5]);
+        buff.skip_oldest(10);
+        assert_eq!(buff.len(), 0);
+    }
 }